@@ -1,14 +1,26 @@
+use std::any::Any;
 use std::io::{Error, ErrorKind, Result};
 use std::ffi::CString;
-use message::Message;
+use crate::message::Message;
 use std::cmp::Ordering;
 use std::ptr;
 use std::boxed::Box;
-use std::sync::{Arc, Mutex};
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex};
+use std::sync::mpsc::{sync_channel, Receiver};
 use std::ops::Deref;
+use std::os::unix::io::{AsRawFd, RawFd};
 use std::slice;
-use std::time::Duration;
-use ffi::*;
+use std::time::{Duration, Instant};
+use crate::ffi::*;
+use futures::channel::mpsc;
+use futures::Stream;
+use tokio::io::unix::AsyncFd;
+
+/// The queue capacity used by `subscribe_broadcast`, `AsyncLcm::subscribe_stream`
+/// and `Selector::register`, matching LCM's own default (see
+/// `subscription_set_queue_capacity`).
+const DEFAULT_QUEUE_CAPACITY: usize = 30;
 
 /// An LCM instance that handles publishing and subscribing,
 /// as well as encoding and decoding messages.
@@ -22,7 +34,7 @@ unsafe impl<'a> Send for Lcm<'a> {}
 
 pub struct LcmSubscription<'a> {
     subscription: *mut lcm_subscription_t,
-    handler: Box<FnMut(*const lcm_recv_buf_t) + 'a>,
+    handler: Box<dyn FnMut(&str, *const lcm_recv_buf_t) + 'a>,
 }
 
 
@@ -62,11 +74,30 @@ impl<'a> Lcm<'a> {
         where M: Message,
               F: FnMut(M) + Send + 'a
     {
-        trace!("Subscribing handler to channel {}", channel);
+        self.subscribe_with_channel::<M, _>(channel, move |_channel, msg| callback(msg))
+    }
 
-        let channel = CString::new(channel).unwrap();
+    /// Subscribes a callback to a channel or, since LCM's C API accepts POSIX regex
+    /// strings in `lcm_subscribe`, a channel pattern such as `"SENSOR_.*"`. The
+    /// callback receives the channel name that actually matched alongside the decoded
+    /// message, so a single wildcard subscription can tell its messages apart.
+    ///
+    /// ```
+    /// # use lcm::Lcm;
+    /// let lcm = Lcm::new().unwrap();
+    /// lcm.subscribe_with_channel("SENSOR_.*", |channel: &str, reading: String| {
+    ///     println!("{}: {}", channel, reading);
+    /// });
+    /// ```
+    pub fn subscribe_with_channel<M, F>(&self, pattern: &str, mut callback: F) -> Arc<LcmSubscription<'a>>
+        where M: Message,
+              F: FnMut(&str, M) + Send + 'a
+    {
+        trace!("Subscribing handler to pattern {}", pattern);
+
+        let pattern = CString::new(pattern).unwrap();
 
-        let handler = Box::new(move |rbuf: *const lcm_recv_buf_t| {
+        let handler = Box::new(move |channel: &str, rbuf: *const lcm_recv_buf_t| {
             trace!("Running handler");
             let mut buf = unsafe {
                 let ref rbuf = *rbuf;
@@ -76,7 +107,7 @@ impl<'a> Lcm<'a> {
             };
             trace!("Decoding buffer: {:?}", buf);
             match M::decode_with_hash(&mut buf) {
-                Ok(msg) => callback(msg),
+                Ok(msg) => callback(channel, msg),
                 Err(_) => error!("Failed to decode buffer: {:?}", buf),
             }
         });
@@ -90,7 +121,7 @@ impl<'a> Lcm<'a> {
 
         let c_subscription = unsafe {
             lcm_subscribe(self.lcm,
-                          channel.as_ptr(),
+                          pattern.as_ptr(),
                           Some(Lcm::handler_callback::<M>),
                           user_data)
         };
@@ -102,6 +133,79 @@ impl<'a> Lcm<'a> {
         subscription
     }
 
+    /// Subscribes to a channel and returns a `Receiver` of decoded messages instead of
+    /// running a callback inline.
+    ///
+    /// The `Receiver` is bounded to `capacity` messages; once full, further messages
+    /// are dropped and logged rather than blocking the FFI callback. This is a
+    /// separate, explicit bound on the Rust-side channel, not the subscription's own
+    /// queue (see `subscription_set_queue_capacity`, which still defaults to
+    /// `DEFAULT_QUEUE_CAPACITY` for the underlying FFI subscription and can be
+    /// adjusted independently via the returned handle).
+    ///
+    /// ```
+    /// # use lcm::Lcm;
+    /// let lcm = Lcm::new().unwrap();
+    /// let (_handler, rx) = lcm.subscribe_channel::<String>("GREETINGS", 30);
+    /// for msg in rx.try_iter() {
+    ///     println!("Hello, {}!", msg);
+    /// }
+    /// ```
+    pub fn subscribe_channel<M>(&self, channel: &str, capacity: usize) -> (Arc<LcmSubscription<'a>>, Receiver<M>)
+        where M: Message + Send + 'a
+    {
+        let (tx, rx) = sync_channel(capacity);
+        let channel_name = channel.to_string();
+
+        let handler = self.subscribe(channel, move |msg: M| {
+            if tx.try_send(msg).is_err() {
+                error!("Subscription queue for channel {} is full; dropping message", channel_name);
+            }
+        });
+
+        (handler, rx)
+    }
+
+    /// Subscribes to `channel` with a single shared FFI handler, fanning out each
+    /// decoded message to every [`BroadcastRx`] cloned from the one returned here.
+    ///
+    /// Unlike `subscribe`, which decodes once per independent subscription, this
+    /// decodes each message exactly once and stores it in a fixed-capacity ring buffer
+    /// that all clones read from at their own pace. A clone that falls more than
+    /// `DEFAULT_QUEUE_CAPACITY` messages behind the writer will have its next `recv`
+    /// return `BroadcastEvent::Lagged` instead of silently skipping messages.
+    ///
+    /// ```
+    /// # use lcm::{Lcm, BroadcastEvent};
+    /// let lcm = Lcm::new().unwrap();
+    /// let (_handler, mut rx) = lcm.subscribe_broadcast::<String>("TELEMETRY");
+    /// let mut rx2 = rx.clone();
+    /// ```
+    pub fn subscribe_broadcast<M>(&self, channel: &str) -> (Arc<LcmSubscription<'a>>, BroadcastRx<M>)
+        where M: Message + Send + Sync + 'a
+    {
+        let ring = Arc::new(BroadcastRing {
+            state: Mutex::new(BroadcastState { queue: VecDeque::new(), write_seq: 0 }),
+            condvar: Condvar::new(),
+            capacity: DEFAULT_QUEUE_CAPACITY,
+        });
+
+        let ring_handler = ring.clone();
+        let handler = self.subscribe(channel, move |msg: M| {
+            let mut state = ring_handler.state.lock().expect("Poisoned mutex");
+            let seq = state.write_seq;
+            state.write_seq += 1;
+            state.queue.push_back((seq, Arc::new(msg)));
+            if state.queue.len() > ring_handler.capacity {
+                state.queue.pop_front();
+            }
+            drop(state);
+            ring_handler.condvar.notify_all();
+        });
+
+        (handler, BroadcastRx { ring, cursor: 0 })
+    }
+
     /// Unsubscribes a message handler.
     ///
     /// ```
@@ -192,6 +296,19 @@ impl<'a> Lcm<'a> {
         }
     }
 
+    /// Dispatches at most one pending message without ever blocking. Returns
+    /// `Ok(false)` if none was pending, rather than the `Err` `handle_timeout` would
+    /// give for a timeout; used by `AsyncLcm` to drain the queue after a readiness
+    /// notification without risking a block on the executor thread.
+    fn try_handle(&self) -> Result<bool> {
+        let result = unsafe { lcm_handle_timeout(self.lcm, 0) };
+        match result.cmp(&0) {
+            Ordering::Less => Err(Error::new(ErrorKind::Other, "LCM Error")),
+            Ordering::Equal => Ok(false),
+            Ordering::Greater => Ok(true),
+        }
+    }
+
     /// Adjusts the maximum number of received messages that can be queued up for a subscription.
     /// The default is `30`.
     ///
@@ -211,14 +328,22 @@ impl<'a> Lcm<'a> {
 
 
     extern "C" fn handler_callback<M>(rbuf: *const lcm_recv_buf_t,
-                                      _: *const ::std::os::raw::c_char,
+                                      channel: *const ::std::os::raw::c_char,
                                       user_data: *mut ::std::os::raw::c_void)
         where M: Message
     {
         trace!("Received data");
         let sub = user_data as *mut LcmSubscription;
         let sub = unsafe { &mut *sub };
-        (sub.handler)(rbuf);
+        let channel = unsafe { ::std::ffi::CStr::from_ptr(channel) };
+        let channel = match channel.to_str() {
+            Ok(channel) => ::std::borrow::Cow::Borrowed(channel),
+            Err(_) => {
+                error!("Channel name {:?} is not valid UTF-8; using lossy conversion", channel);
+                channel.to_string_lossy()
+            }
+        };
+        (sub.handler)(&channel, rbuf);
     }
 }
 
@@ -229,7 +354,289 @@ impl<'a> Drop for Lcm<'a> {
     }
 }
 
+/// Lets `AsyncFd` treat LCM's pollable file descriptor as an `AsRawFd` value
+/// without owning (and closing) it itself; `Lcm::drop` already owns its lifecycle.
+struct BorrowedFd(RawFd);
+
+impl AsRawFd for BorrowedFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0
+    }
+}
+
+/// Wraps an `Lcm` instance so it can be driven from a tokio runtime instead of
+/// blocking a dedicated thread in `lcm_handle`.
+///
+/// `get_fileno` exposes the file descriptor LCM itself polls internally; `AsyncLcm`
+/// registers that descriptor with tokio's reactor and only dispatches messages (via
+/// non-blocking `lcm_handle_timeout(0)` calls) once the descriptor is readable, so an `Lcm` can participate in `tokio::select!`
+/// alongside timers and network sockets.
+pub struct AsyncLcm<'a> {
+    lcm: Arc<Lcm<'a>>,
+    fd: AsyncFd<BorrowedFd>,
+}
+
+impl<'a> AsyncLcm<'a> {
+    /// Wraps `lcm` for asynchronous handling.
+    pub fn new(lcm: Arc<Lcm<'a>>) -> Result<AsyncLcm<'a>> {
+        let fd = AsyncFd::new(BorrowedFd(lcm.get_fileno()))?;
+        Ok(AsyncLcm { lcm, fd })
+    }
+
+    /// Waits for the LCM file descriptor to become readable, then drains every
+    /// pending message via non-blocking `lcm_handle_timeout(0)` calls before
+    /// returning, so the executor thread is never blocked inside `lcm_handle`
+    /// waiting on a message that the readiness notification already promised.
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use lcm::{Lcm, AsyncLcm};
+    /// # async fn example() -> std::io::Result<()> {
+    /// let lcm = Arc::new(Lcm::new().unwrap());
+    /// let mut async_lcm = AsyncLcm::new(lcm.clone())?;
+    /// loop {
+    ///     async_lcm.handle().await?;
+    /// }
+    /// # }
+    /// ```
+    pub async fn handle(&mut self) -> Result<()> {
+        let mut guard = self.fd.readable().await?;
+
+        loop {
+            match self.lcm.try_handle() {
+                Ok(true) => continue,
+                Ok(false) => {
+                    guard.clear_ready();
+                    return Ok(());
+                }
+                Err(err) => {
+                    guard.clear_ready();
+                    return Err(err);
+                }
+            }
+        }
+    }
+
+    /// Subscribes to `channel`, returning a stream of decoded messages.
+    ///
+    /// The FFI handler pushes each decoded message into a `futures::channel::mpsc`
+    /// sender; the stream only makes progress while this `AsyncLcm`'s `handle` loop is
+    /// being driven (typically from a background task), since that's what actually
+    /// dispatches LCM's pending messages.
+    pub fn subscribe_stream<M>(&self, channel: &str) -> (Arc<LcmSubscription<'a>>, impl Stream<Item = M>)
+        where M: Message + Send + 'a
+    {
+        let (mut tx, rx) = mpsc::channel(DEFAULT_QUEUE_CAPACITY);
+        let channel_name = channel.to_string();
+
+        let handler = self.lcm.subscribe(channel, move |msg: M| {
+            if tx.try_send(msg).is_err() {
+                error!("Subscription stream for channel {} is full; dropping message", channel_name);
+            }
+        });
+
+        (handler, rx)
+    }
+}
 
+struct BroadcastState<M> {
+    queue: VecDeque<(usize, Arc<M>)>,
+    write_seq: usize,
+}
+
+struct BroadcastRing<M> {
+    state: Mutex<BroadcastState<M>>,
+    condvar: Condvar,
+    capacity: usize,
+}
+
+/// An item produced by [`BroadcastRx::recv`].
+pub enum BroadcastEvent<M> {
+    /// A decoded message.
+    Message(Arc<M>),
+    /// The receiver fell behind the writer by more than the ring's capacity, and this
+    /// many messages were dropped before it could read them. The receiver resumes at
+    /// the oldest message still retained in the ring.
+    Lagged(usize),
+}
+
+/// A cheaply-clonable handle to a [`Lcm::subscribe_broadcast`] subscription.
+///
+/// Each clone tracks its own read cursor into the shared ring buffer, so a slow
+/// consumer falls behind independently instead of blocking faster ones.
+pub struct BroadcastRx<M> {
+    ring: Arc<BroadcastRing<M>>,
+    cursor: usize,
+}
+
+impl<M> BroadcastRx<M> {
+    /// Checks the ring for a message or lag this receiver hasn't seen yet, without
+    /// waiting. Returns `None` if the receiver is caught up with the writer.
+    fn poll(&mut self, state: &BroadcastState<M>) -> Option<BroadcastEvent<M>> {
+        let front_seq = state.queue.front().map(|&(seq, _)| seq);
+
+        if let Some(front_seq) = front_seq {
+            if self.cursor < front_seq {
+                let lagged = front_seq - self.cursor;
+                self.cursor = front_seq;
+                return Some(BroadcastEvent::Lagged(lagged));
+            }
+        }
+
+        if self.cursor < state.write_seq {
+            let offset = self.cursor - front_seq.unwrap_or(self.cursor);
+            let (seq, message) = state.queue[offset].clone();
+            debug_assert_eq!(seq, self.cursor);
+            self.cursor += 1;
+            return Some(BroadcastEvent::Message(message));
+        }
+
+        None
+    }
+
+    /// Blocks until the next message (or lag notification) is available.
+    ///
+    /// This blocks indefinitely if the publishing `Lcm` is dropped without another
+    /// message ever arriving; use `recv_timeout` if that's a concern.
+    pub fn recv(&mut self) -> BroadcastEvent<M> {
+        // Clone the `Arc` up front so the `MutexGuard` below borrows from `ring`
+        // instead of from `self`, leaving `self` free for `self.poll(&state)`.
+        let ring = self.ring.clone();
+        let mut state = ring.state.lock().expect("Poisoned mutex");
+        loop {
+            if let Some(event) = self.poll(&state) {
+                return event;
+            }
+            state = ring.condvar.wait(state).expect("Poisoned mutex");
+        }
+    }
+
+    /// Like `recv`, but gives up and returns an error after `timeout` instead of
+    /// blocking forever, so a consumer can notice a publisher that went away.
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Result<BroadcastEvent<M>> {
+        let ring = self.ring.clone();
+        let deadline = Instant::now() + timeout;
+        let mut state = ring.state.lock().expect("Poisoned mutex");
+        loop {
+            if let Some(event) = self.poll(&state) {
+                return Ok(event);
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining == Duration::new(0, 0) {
+                return Err(Error::new(ErrorKind::Other, "BroadcastRx timed out"));
+            }
+
+            let (guard, timeout_result) = ring.condvar.wait_timeout(state, remaining).expect("Poisoned mutex");
+            state = guard;
+            if timeout_result.timed_out() {
+                return Err(Error::new(ErrorKind::Other, "BroadcastRx timed out"));
+            }
+        }
+    }
+}
+
+impl<M> Clone for BroadcastRx<M> {
+    fn clone(&self) -> BroadcastRx<M> {
+        BroadcastRx { ring: self.ring.clone(), cursor: self.cursor }
+    }
+}
+
+/// An item produced by [`Selector::recv_timeout`]: which registered slot (in
+/// registration order) produced a message, plus the decoded message itself, boxed
+/// as `Any` for the caller to downcast back to the type it was registered with.
+pub struct SelectEvent {
+    /// Index of the channel slot, in the order it was passed to `Selector::register`.
+    pub index: usize,
+    /// The decoded message, boxed as `Any`; downcast it back to the registered type.
+    pub message: Box<dyn Any + Send>,
+}
+
+trait SelectorChannel {
+    fn try_recv(&self) -> Option<Box<dyn Any + Send>>;
+}
+
+impl<M: Send + 'static> SelectorChannel for Receiver<M> {
+    fn try_recv(&self) -> Option<Box<dyn Any + Send>> {
+        match Receiver::try_recv(self) {
+            Ok(msg) => Some(Box::new(msg)),
+            Err(_) => None,
+        }
+    }
+}
+
+/// Multiplexes several typed subscriptions on one `Lcm` instance, dispatching each
+/// incoming message to the registered channel it belongs to instead of LCM's opaque
+/// single-dispatch `handle`.
+///
+/// Messages are handed out in round-robin order across registered channels, so a
+/// high-rate channel cannot starve a low-rate one.
+pub struct Selector<'lcm, 'a> {
+    lcm: &'lcm Lcm<'a>,
+    channels: Vec<(Arc<LcmSubscription<'a>>, Box<dyn SelectorChannel + Send>)>,
+    next: usize,
+}
+
+impl<'lcm, 'a> Selector<'lcm, 'a> {
+    /// Creates an empty selector over `lcm`.
+    ///
+    /// `lcm` and the selector's own subscriptions are tracked with separate
+    /// lifetimes (`'lcm` and `'a`) rather than a single one: unifying them would
+    /// require `Lcm`'s `Drop` impl to be known to have finished before any borrow
+    /// of `lcm` could end, which isn't satisfiable while a `Selector` and the `Lcm`
+    /// it borrows are both still in scope.
+    pub fn new(lcm: &'lcm Lcm<'a>) -> Selector<'lcm, 'a> {
+        Selector { lcm, channels: Vec::new(), next: 0 }
+    }
+
+    /// Registers a typed subscription with this selector, returning the slot index
+    /// that `SelectEvent::index` will report for messages on `channel`.
+    pub fn register<M>(&mut self, channel: &str) -> usize
+        where M: Message + Send + 'static
+    {
+        let (_handler, rx) = self.lcm.subscribe_channel::<M>(channel, DEFAULT_QUEUE_CAPACITY);
+        self.channels.push((_handler, Box::new(rx)));
+        self.channels.len() - 1
+    }
+
+    /// Waits up to `timeout` for any registered channel to produce a message,
+    /// returning the first one ready in round-robin order.
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Result<SelectEvent> {
+        let deadline = Instant::now() + timeout;
+        let num_channels = self.channels.len();
+
+        loop {
+            for _ in 0..num_channels {
+                let index = self.next;
+                self.next = (self.next + 1) % num_channels.max(1);
+                if let Some(message) = self.channels[index].1.try_recv() {
+                    return Ok(SelectEvent { index, message });
+                }
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining == Duration::new(0, 0) {
+                return Err(Error::new(ErrorKind::Other, "Selector timed out"));
+            }
+            if !wait_readable(self.lcm.get_fileno(), remaining)? {
+                return Err(Error::new(ErrorKind::Other, "Selector timed out"));
+            }
+            while self.lcm.handle_timeout(Duration::from_millis(0)).is_ok() {}
+        }
+    }
+}
+
+/// Waits up to `timeout` for `fd` to become readable. Returns `Ok(false)` on timeout.
+fn wait_readable(fd: ::std::os::raw::c_int, timeout: Duration) -> Result<bool> {
+    let mut fds = libc::pollfd { fd, events: libc::POLLIN, revents: 0 };
+    let timeout_ms = (timeout.as_secs() * 1000) as i32 + timeout.subsec_millis() as i32;
+    let result = unsafe { libc::poll(&mut fds, 1, timeout_ms) };
+    match result.cmp(&0) {
+        Ordering::Less => Err(Error::new(ErrorKind::Other, "poll() failed")),
+        Ordering::Equal => Ok(false),
+        Ordering::Greater => Ok(true),
+    }
+}
 
 #[cfg(test)]
 ///
@@ -238,6 +645,18 @@ impl<'a> Drop for Lcm<'a> {
 mod test {
     use std::sync::Arc;
     use super::*;
+    use futures::StreamExt;
+
+    /// Creates an `Lcm` backed by LCM's `memq://` provider, which dispatches
+    /// published messages in-process instead of over multicast UDP. This lets
+    /// tests drive `publish`/`handle_timeout` end to end deterministically,
+    /// without depending on a real network interface.
+    fn memq_lcm() -> Lcm<'static> {
+        let provider = CString::new("memq://").unwrap();
+        let lcm = unsafe { lcm_create(provider.as_ptr()) };
+        assert!(!lcm.is_null(), "memq:// provider should always be available");
+        Lcm { lcm, subscriptions: Mutex::new(Vec::new()) }
+    }
 
     #[test]
     fn initialized() {
@@ -261,4 +680,162 @@ mod test {
         let subs = lcm.subscriptions.lock().unwrap();
         assert_eq!(subs.len(), 0);
     }
+
+    #[test]
+    fn test_subscribe_channel_honors_capacity() {
+        let lcm = memq_lcm();
+        let (_handler, rx) = lcm.subscribe_channel::<String>("channel", 1);
+
+        lcm.publish("channel", &"one".to_string()).unwrap();
+        lcm.publish("channel", &"two".to_string()).unwrap();
+
+        // Both publishes are already queued on the memq provider; draining them in
+        // one pass exercises the capacity-1 drop-on-full bound for real.
+        while lcm.handle_timeout(Duration::from_millis(10)).is_ok() {}
+
+        assert_eq!(rx.try_recv().unwrap(), "one");
+        assert!(rx.try_recv().is_err(), "second message should have been dropped once the queue is full");
+    }
+
+    #[test]
+    fn test_subscribe_with_channel_receives_channel_name_and_message() {
+        let lcm = memq_lcm();
+        let (tx, rx) = sync_channel(1);
+        let _handler = lcm.subscribe_with_channel("channel", move |channel: &str, msg: String| {
+            tx.send((channel.to_string(), msg)).unwrap();
+        });
+
+        lcm.publish("channel", &"hello".to_string()).unwrap();
+        lcm.handle_timeout(Duration::from_millis(10)).unwrap();
+
+        let (channel, msg) = rx.try_recv().unwrap();
+        assert_eq!(channel, "channel");
+        assert_eq!(msg, "hello");
+    }
+
+    #[test]
+    fn test_broadcast_rx_reports_lag_then_resumes() {
+        // Exercise the ring directly rather than through `subscribe_broadcast`, since
+        // the arithmetic in `BroadcastRx::poll` doesn't need a live FFI round trip.
+        let ring = Arc::new(BroadcastRing {
+            state: Mutex::new(BroadcastState { queue: VecDeque::new(), write_seq: 0 }),
+            condvar: Condvar::new(),
+            capacity: 2,
+        });
+
+        {
+            let mut state = ring.state.lock().unwrap();
+            for i in 0..5 {
+                let seq = state.write_seq;
+                state.write_seq += 1;
+                state.queue.push_back((seq, Arc::new(i)));
+                if state.queue.len() > ring.capacity {
+                    state.queue.pop_front();
+                }
+            }
+        }
+
+        let mut rx = BroadcastRx { ring: ring, cursor: 0 };
+
+        match rx.recv() {
+            BroadcastEvent::Lagged(n) => assert_eq!(n, 3),
+            BroadcastEvent::Message(_) => panic!("expected Lagged"),
+        }
+        match rx.recv() {
+            BroadcastEvent::Message(msg) => assert_eq!(*msg, 3),
+            BroadcastEvent::Lagged(_) => panic!("expected Message"),
+        }
+        match rx.recv() {
+            BroadcastEvent::Message(msg) => assert_eq!(*msg, 4),
+            BroadcastEvent::Lagged(_) => panic!("expected Message"),
+        }
+    }
+
+    #[test]
+    fn test_subscribe_broadcast_fans_out_to_every_clone() {
+        let lcm = memq_lcm();
+        let (_handler, mut rx) = lcm.subscribe_broadcast::<String>("channel");
+        let mut rx2 = rx.clone();
+
+        lcm.publish("channel", &"hello".to_string()).unwrap();
+        lcm.handle_timeout(Duration::from_millis(10)).unwrap();
+
+        match rx.recv() {
+            BroadcastEvent::Message(msg) => assert_eq!(*msg, "hello"),
+            BroadcastEvent::Lagged(_) => panic!("expected Message"),
+        }
+        match rx2.recv() {
+            BroadcastEvent::Message(msg) => assert_eq!(*msg, "hello"),
+            BroadcastEvent::Lagged(_) => panic!("expected Message"),
+        }
+    }
+
+    #[test]
+    fn test_subscribe_stream_yields_decoded_messages() {
+        // `AsyncFd::new` needs a current tokio reactor, so enter the runtime first.
+        let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        let _guard = rt.enter();
+
+        let lcm = Arc::new(memq_lcm());
+        let mut async_lcm = AsyncLcm::new(lcm.clone()).unwrap();
+        let (_handler, mut stream) = async_lcm.subscribe_stream::<String>("channel");
+
+        lcm.publish("channel", &"hello".to_string()).unwrap();
+
+        let msg = rt.block_on(async {
+            async_lcm.handle().await.unwrap();
+            stream.next().await.unwrap()
+        });
+        assert_eq!(msg, "hello");
+    }
+
+    #[test]
+    fn test_broadcast_rx_recv_timeout_times_out_when_idle() {
+        let ring = Arc::new(BroadcastRing {
+            state: Mutex::new(BroadcastState { queue: VecDeque::new(), write_seq: 0 }),
+            condvar: Condvar::new(),
+            capacity: 2,
+        });
+        let mut rx: BroadcastRx<i32> = BroadcastRx { ring: ring, cursor: 0 };
+
+        assert!(rx.recv_timeout(Duration::from_millis(10)).is_err());
+    }
+
+    #[test]
+    fn test_selector_dispatches_in_round_robin_order() {
+        // Feed the selector's channel slots directly rather than through `register`,
+        // so this only exercises the round-robin + downcast dispatch in
+        // `recv_timeout`, not a real FFI publish/subscribe round trip.
+        let lcm = Lcm::new().unwrap();
+        let mut selector = Selector::new(&lcm);
+
+        let (tx_a, rx_a) = sync_channel::<i32>(2);
+        let (tx_b, rx_b) = sync_channel::<i32>(2);
+        selector.channels.push((lcm.subscribe("a", |_: String| {}), Box::new(rx_a)));
+        selector.channels.push((lcm.subscribe("b", |_: String| {}), Box::new(rx_b)));
+
+        tx_a.send(1).unwrap();
+        tx_b.send(2).unwrap();
+
+        let first = selector.recv_timeout(Duration::from_millis(10)).unwrap();
+        assert_eq!(first.index, 0);
+        assert_eq!(*first.message.downcast::<i32>().unwrap(), 1);
+
+        let second = selector.recv_timeout(Duration::from_millis(10)).unwrap();
+        assert_eq!(second.index, 1);
+        assert_eq!(*second.message.downcast::<i32>().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_selector_register_receives_real_publish() {
+        let lcm = memq_lcm();
+        let mut selector = Selector::new(&lcm);
+        let index = selector.register::<String>("channel");
+
+        lcm.publish("channel", &"hello".to_string()).unwrap();
+
+        let event = selector.recv_timeout(Duration::from_millis(10)).unwrap();
+        assert_eq!(event.index, index);
+        assert_eq!(*event.message.downcast::<String>().unwrap(), "hello");
+    }
 }
@@ -0,0 +1,25 @@
+//! Rust bindings for LCM (Lightweight Communications and Marshalling).
+
+#[macro_use]
+extern crate error_chain;
+#[macro_use]
+extern crate log;
+extern crate futures;
+extern crate tokio;
+
+mod ffi;
+mod message;
+mod error;
+mod lcm;
+
+pub use message::Message;
+pub use error::*;
+pub use lcm::{
+    AsyncLcm,
+    BroadcastEvent,
+    BroadcastRx,
+    Lcm,
+    LcmSubscription,
+    SelectEvent,
+    Selector,
+};